@@ -0,0 +1,85 @@
+//! Asynchronous [`Source`]s, for config backends that require awaiting I/O.
+//!
+//! [`Source::provide`] is synchronous, which rules out secret stores (Vault, AWS/GCP
+//! secret managers) and HTTP-backed config services that only expose an async API.
+//! [`AsyncSource`] mirrors `Source` for that case; register one with
+//! [`ConfigBuilder::override_with_async`](crate::ConfigBuilder::override_with_async) and
+//! resolve it, alongside the synchronous sources, via
+//! [`ConfigBuilder::try_build_async`](crate::ConfigBuilder::try_build_async).
+
+use std::{error::Error, future::Future, pin::Pin};
+
+use crate::ConfigurationBuilder;
+
+/// Boxes `fut` as [`AsyncProvideFn`]'s future, so [`ConfigBuilder::override_with_async`]
+/// doesn't have to spell out the trait-object coercion by hand.
+///
+/// [`ConfigBuilder::override_with_async`]: crate::ConfigBuilder::override_with_async
+pub(crate) fn box_future<B>(
+    fut: impl Future<Output = Result<B, Box<dyn Error + Sync + Send>>> + Send + 'static,
+) -> Pin<Box<dyn Future<Output = Result<B, Box<dyn Error + Sync + Send>>> + Send>> {
+    Box::pin(fut)
+}
+
+/// An `Rc`-shared closure that creates a fresh boxed future producing a builder on each
+/// call, used internally by
+/// [`ConfigBuilder`](crate::ConfigBuilder) to hold a registered [`AsyncSource`] without
+/// requiring `AsyncSource` itself to be object-safe (its `provide` method, like
+/// [`Source::provide`](crate::Source::provide), is generic over the target builder). `Rc`,
+/// rather than `Box`, so `ConfigBuilder` can derive a cheap `Clone`.
+pub(crate) type AsyncProvideFn<B> = std::rc::Rc<
+    dyn Fn() -> Pin<Box<dyn Future<Output = Result<B, Box<dyn Error + Sync + Send>>> + Send>>,
+>;
+
+/// An async counterpart to [`Source`](crate::Source).
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "async")]
+/// # async fn example() {
+/// use confik::{AsyncSource, ConfigBuilder, Configuration};
+///
+/// struct Remote(u16);
+///
+/// #[async_trait::async_trait]
+/// impl AsyncSource for Remote {
+///     async fn provide<T: confik::ConfigurationBuilder>(
+///         &self,
+///     ) -> Result<T, Box<dyn std::error::Error + Sync + Send>> {
+///         Ok(serde_json::from_value(serde_json::json!({ "port": self.0 }))?)
+///     }
+/// }
+///
+/// #[derive(Configuration)]
+/// struct Config {
+///     port: u16,
+/// }
+///
+/// let config = ConfigBuilder::<Config>::default()
+///     .override_with_async(Remote(1234))
+///     .try_build_async()
+///     .await
+///     .unwrap();
+///
+/// assert_eq!(config.port, 1234);
+/// # }
+/// ```
+///
+/// # Secrets
+///
+/// Async sources typically carry credentials (API tokens for a secret store, etc.), so
+/// [`allows_secrets`](Self::allows_secrets) defaults to `true`. Implementors that don't
+/// carry secrets should override it to return `false`.
+#[async_trait::async_trait]
+pub trait AsyncSource {
+    /// Whether this source is allowed to contain secret values.
+    ///
+    /// Defaults to `true`, since async sources most often front a secret store.
+    fn allows_secrets(&self) -> bool {
+        true
+    }
+
+    /// Asynchronously provides a value for a [`ConfigurationBuilder`].
+    async fn provide<T: ConfigurationBuilder>(&self) -> Result<T, Box<dyn Error + Sync + Send>>;
+}
@@ -0,0 +1,69 @@
+//! Errors produced while merging [`Source`](crate::Source)s into a
+//! [`Configuration`](crate::Configuration).
+
+use std::fmt;
+
+use crate::provenance::Origin;
+
+/// An error produced by [`ConfigBuilder::try_build`](crate::ConfigBuilder::try_build) (or
+/// its async/sync siblings).
+///
+/// # Examples
+///
+/// ```
+/// use confik::{provenance::Origin, Error};
+///
+/// let err = Error::MissingValue {
+///     path: "port".to_string(),
+///     consulted: vec![Origin::new("EnvSource", "PORT")],
+/// };
+///
+/// assert_eq!(
+///     err.to_string(),
+///     "missing required value for `port` (consulted: EnvSource:PORT)",
+/// );
+/// ```
+#[derive(Debug)]
+pub enum Error {
+    /// A [`Source`](crate::Source) failed to provide its values (e.g. a file couldn't be
+    /// parsed).
+    Source(Box<dyn std::error::Error + Sync + Send>),
+    /// A required field was never provided by any registered source.
+    MissingValue {
+        /// The dotted key path of the missing field.
+        path: String,
+        /// The sources that were consulted while building this config, in registration
+        /// order, for whichever of them recorded an [`Origin`].
+        consulted: Vec<Origin>,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Source(err) => write!(f, "source error: {err}"),
+            Self::MissingValue { path, consulted } => {
+                write!(f, "missing required value for `{path}`")?;
+                if !consulted.is_empty() {
+                    write!(f, " (consulted: ")?;
+                    for (i, origin) in consulted.iter().enumerate() {
+                        if i > 0 {
+                            write!(f, ", ")?;
+                        }
+                        write!(f, "{origin}")?;
+                    }
+                    write!(f, ")")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<Box<dyn std::error::Error + Sync + Send>> for Error {
+    fn from(err: Box<dyn std::error::Error + Sync + Send>) -> Self {
+        Self::Source(err)
+    }
+}
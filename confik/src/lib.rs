@@ -0,0 +1,304 @@
+//! confik: layered, typed configuration assembled from one or more [`Source`]s.
+//!
+//! A [`Configuration`]-deriving type describes its shape; a [`ConfigBuilder`] merges
+//! values for it from any number of [`Source`]s (env vars, files, literals, ...),
+//! applied in registration order, into a finished value via [`ConfigBuilder::try_build`].
+
+use std::error::Error as StdError;
+
+pub use error::Error;
+pub use provenance::Origin;
+pub use secret::Secret;
+pub use sources::{EnvSource, LiteralSource, ProfiledSource};
+
+#[cfg(feature = "async")]
+pub use async_source::AsyncSource;
+#[cfg(feature = "watch")]
+pub use reload::ReloadableConfig;
+
+#[cfg(feature = "async")]
+mod async_source;
+mod error;
+pub mod provenance;
+#[cfg(feature = "watch")]
+mod reload;
+mod secret;
+pub mod sources;
+
+/// A type whose values can be assembled from one or more [`Source`]s.
+///
+/// Normally implemented via `#[derive(Configuration)]` rather than by hand.
+pub trait Configuration: Sized {
+    /// The partial-state counterpart of this type, accumulated across sources before
+    /// [`ConfigurationBuilder::try_build`] produces a `Self`.
+    type Builder: ConfigurationBuilder<Target = Self>;
+}
+
+/// The partial-state counterpart of a [`Configuration`], produced by merging each
+/// registered [`Source`]'s contribution in turn.
+pub trait ConfigurationBuilder: Default + serde::de::DeserializeOwned {
+    /// The [`Configuration`] this builder ultimately produces.
+    type Target;
+
+    /// Merges `other` on top of `self`: fields `other` provided win, everything else
+    /// falls back to whatever `self` already had.
+    fn merge(self, other: Self) -> Self;
+
+    /// Finalizes the builder into its target, failing if a required field was never
+    /// provided by any source.
+    fn try_build(self) -> Result<Self::Target, Error>;
+}
+
+/// A provider of configuration values for a [`ConfigurationBuilder`].
+pub trait Source {
+    /// Whether this source is allowed to contain secret values.
+    fn allows_secrets(&self) -> bool;
+
+    /// Provides a (partial) value for a [`ConfigurationBuilder`].
+    fn provide<T: ConfigurationBuilder>(&self) -> Result<T, Box<dyn StdError + Sync + Send>>;
+
+    /// The [`Origin`] this source attaches to the value it contributed for `path`, if it
+    /// tracks provenance and actually has a value there.
+    ///
+    /// Defaults to `None`; sources that want to participate in provenance-tagged errors
+    /// (see [`ConfigBuilder::try_build`]'s `consulted` list) should override this.
+    fn origin_for(&self, _path: &str) -> Option<Origin> {
+        None
+    }
+}
+
+type ProvideFn<B> = std::rc::Rc<dyn Fn() -> Result<B, Box<dyn StdError + Sync + Send>>>;
+type OriginProbeFn = std::rc::Rc<dyn Fn(&str) -> Option<Origin>>;
+
+/// Builds a [`Configuration`] by merging values from any number of registered
+/// [`Source`]s.
+///
+/// Merge precedence, lowest to highest: [`set_default`](Self::set_default) values,
+/// [`override_with`](Self::override_with)/[`override_with_profiled`](Self::override_with_profiled)
+/// sources in registration order (plus, behind the `async` feature,
+/// [`override_with_async`](Self::override_with_async) sources, awaited after the
+/// synchronous ones by [`try_build_async`](Self::try_build_async)), then
+/// [`set_override`](Self::set_override) values.
+///
+/// # Examples
+///
+/// ```
+/// use confik::{ConfigBuilder, Configuration, EnvSource};
+///
+/// #[derive(Configuration)]
+/// struct Config {
+///     port: u16,
+/// }
+///
+/// std::env::set_var("PORT", "1234");
+///
+/// let config = ConfigBuilder::<Config>::default()
+///     .override_with(EnvSource::new())
+///     .try_build()
+///     .unwrap();
+///
+/// assert_eq!(config.port, 1234);
+/// ```
+pub struct ConfigBuilder<T: Configuration> {
+    defaults: Vec<ProvideFn<T::Builder>>,
+    sources: Vec<ProvideFn<T::Builder>>,
+    overrides: Vec<ProvideFn<T::Builder>>,
+    origin_probes: Vec<OriginProbeFn>,
+    profile: Option<String>,
+    #[cfg(feature = "async")]
+    async_sources: Vec<async_source::AsyncProvideFn<T::Builder>>,
+}
+
+// Derived `Clone` would add a spurious `T: Clone` bound; every field here is an
+// `Rc`-shared closure or the profile name, cheap and always cloneable regardless of `T`.
+impl<T: Configuration> Clone for ConfigBuilder<T> {
+    fn clone(&self) -> Self {
+        Self {
+            defaults: self.defaults.clone(),
+            sources: self.sources.clone(),
+            overrides: self.overrides.clone(),
+            origin_probes: self.origin_probes.clone(),
+            profile: self.profile.clone(),
+            #[cfg(feature = "async")]
+            async_sources: self.async_sources.clone(),
+        }
+    }
+}
+
+impl<T: Configuration> Default for ConfigBuilder<T> {
+    fn default() -> Self {
+        Self {
+            defaults: Vec::new(),
+            sources: Vec::new(),
+            overrides: Vec::new(),
+            origin_probes: Vec::new(),
+            profile: None,
+            #[cfg(feature = "async")]
+            async_sources: Vec::new(),
+        }
+    }
+}
+
+impl<T> ConfigBuilder<T>
+where
+    T: Configuration,
+    T::Builder: 'static,
+{
+    /// Registers `source`, merged in at the point this method is called relative to
+    /// other sources.
+    pub fn override_with<S: Source + 'static>(mut self, source: S) -> Self {
+        let source = std::rc::Rc::new(source);
+        let provide_source = std::rc::Rc::clone(&source);
+        self.sources
+            .push(std::rc::Rc::new(move || provide_source.provide::<T::Builder>()));
+        self.origin_probes
+            .push(std::rc::Rc::new(move |path| source.origin_for(path)));
+        self
+    }
+
+    /// Sets the active deployment profile, consulted by
+    /// [`override_with_profiled`](Self::override_with_profiled) calls made *after* this
+    /// one.
+    ///
+    /// Falls back to the `CONFIK_PROFILE` env var, then to an empty profile, if this is
+    /// never called.
+    pub fn with_profile(mut self, profile: impl Into<String>) -> Self {
+        self.profile = Some(profile.into());
+        self
+    }
+
+    /// Registers a [`sources::ProfileAwareSource`], scoped to the active profile (set via
+    /// [`with_profile`](Self::with_profile), else the `CONFIK_PROFILE` env var) by
+    /// wrapping it in a [`ProfiledSource`].
+    ///
+    /// Slots in alongside plain [`override_with`](Self::override_with) sources at the
+    /// point this is called, keeping the overall order at defaults → base → active
+    /// profile → overrides as long as it's registered after any base-only sources and
+    /// before any [`set_override`](Self::set_override) calls.
+    pub fn override_with_profiled<S: sources::ProfileAwareSource + 'static>(
+        self,
+        source: S,
+    ) -> Self {
+        let profile = self
+            .profile
+            .clone()
+            .unwrap_or_else(|| std::env::var(sources::CONFIK_PROFILE_ENV).unwrap_or_default());
+        self.override_with(ProfiledSource::new(source, profile))
+    }
+
+    /// Sets a default for `path` (a dotted key path, e.g. `"database.port"`),
+    /// overridable by every source registered via [`override_with`](Self::override_with)
+    /// and friends, and by [`set_override`](Self::set_override).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use confik::{ConfigBuilder, Configuration};
+    ///
+    /// #[derive(Configuration)]
+    /// struct Config {
+    ///     port: u16,
+    /// }
+    ///
+    /// let config = ConfigBuilder::<Config>::default()
+    ///     .set_default("port", 8080)
+    ///     .unwrap()
+    ///     .set_override("port", 9090)
+    ///     .unwrap()
+    ///     .try_build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.port, 9090);
+    /// ```
+    pub fn set_default(
+        mut self,
+        path: impl Into<String>,
+        value: impl serde::Serialize,
+    ) -> Result<Self, Error> {
+        let source = LiteralSource::new(path, value).map_err(|err| Error::Source(Box::new(err)))?;
+        self.defaults
+            .push(std::rc::Rc::new(move || source.provide::<T::Builder>()));
+        Ok(self)
+    }
+
+    /// Sets an override for `path` (a dotted key path, e.g. `"database.port"`), beating
+    /// every other registered source.
+    pub fn set_override(
+        mut self,
+        path: impl Into<String>,
+        value: impl serde::Serialize,
+    ) -> Result<Self, Error> {
+        let source = LiteralSource::new(path, value).map_err(|err| Error::Source(Box::new(err)))?;
+        self.overrides
+            .push(std::rc::Rc::new(move || source.provide::<T::Builder>()));
+        Ok(self)
+    }
+
+    /// Merges every registered synchronous source, in precedence order, into `T`.
+    ///
+    /// Sources registered via `override_with_async` are not merged here; only
+    /// `try_build_async` awaits them.
+    pub fn try_build(self) -> Result<T, Error> {
+        let mut builder = T::Builder::default();
+        for source in self.defaults.iter().chain(&self.sources).chain(&self.overrides) {
+            builder = builder.merge(source()?);
+        }
+        builder.try_build().map_err(|err| self.attach_origins(err))
+    }
+
+    /// Enriches a [`MissingValue`](Error::MissingValue) error by asking every registered
+    /// source's [`Source::origin_for`] whether it has a value at the missing path, and
+    /// appending the [`Origin`] of each one that does. Sources added via
+    /// [`set_default`](Self::set_default)/[`set_override`](Self::set_override) are not
+    /// asked.
+    fn attach_origins(&self, err: Error) -> Error {
+        match err {
+            Error::MissingValue { path, mut consulted } => {
+                consulted.extend(self.origin_probes.iter().filter_map(|probe| probe(&path)));
+                Error::MissingValue { path, consulted }
+            }
+            other => other,
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T> ConfigBuilder<T>
+where
+    T: Configuration,
+    T::Builder: 'static,
+{
+    /// Registers an [`AsyncSource`], awaited (in registration order, alongside other
+    /// async sources) by [`try_build_async`](Self::try_build_async).
+    ///
+    /// `source` is wrapped in an `Arc` (like [`override_with`](Self::override_with) wraps
+    /// its source in an `Rc`) and cloned into an owned future on every call, rather than
+    /// borrowed: `AsyncSource::provide`'s returned future otherwise borrows `source` for
+    /// the call, which can't satisfy the `'static` bound the boxed future needs once it
+    /// outlives that call.
+    pub fn override_with_async<S: AsyncSource + Send + Sync + 'static>(mut self, source: S) -> Self {
+        let source = std::sync::Arc::new(source);
+        self.async_sources.push(std::rc::Rc::new(move || {
+            let source = std::sync::Arc::clone(&source);
+            async_source::box_future(async move { source.provide::<T::Builder>().await })
+        }));
+        self
+    }
+
+    /// Merges every registered source, synchronous and async, in precedence order, into
+    /// `T`, awaiting the async sources (in registration order) after the synchronous
+    /// ones.
+    pub async fn try_build_async(self) -> Result<T, Error> {
+        let mut builder = T::Builder::default();
+        for source in self.defaults.iter().chain(&self.sources) {
+            builder = builder.merge(source()?);
+        }
+        for source in &self.async_sources {
+            builder = builder.merge(source().await?);
+        }
+        for source in &self.overrides {
+            builder = builder.merge(source()?);
+        }
+        builder.try_build().map_err(|err| self.attach_origins(err))
+    }
+}
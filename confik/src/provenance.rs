@@ -0,0 +1,47 @@
+//! Provenance tracking for values loaded from a [`Source`](crate::Source).
+//!
+//! Layered configs make it easy to lose track of *where* a given value came from: was
+//! `port` set by an environment variable, or by a file source further down the merge
+//! chain? An [`Origin`] is a small tag that a [`Source`] can attach to the values it
+//! contributes, so that merge conflicts and missing-required-field errors can name the
+//! contributing source(s) instead of just the key path.
+
+use std::fmt;
+
+/// Identifies the [`Source`](crate::Source) (and, where applicable, the key within that
+/// source) that contributed a value to a [`ConfigBuilder`](crate::ConfigBuilder).
+///
+/// Sources that don't opt into provenance tracking simply don't attach one; `Origin` is
+/// purely additive and has no effect on how values are merged.
+///
+/// # Examples
+///
+/// ```
+/// use confik::provenance::Origin;
+///
+/// let origin = Origin::new("EnvSource", "PORT");
+/// assert_eq!(origin.to_string(), "EnvSource:PORT");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Origin {
+    /// A short, human-readable name for the source (e.g. `"EnvSource"`).
+    pub source: String,
+    /// The key path within that source that produced the value (e.g. `"PORT"`).
+    pub key: String,
+}
+
+impl Origin {
+    /// Creates a new [`Origin`] tag.
+    pub fn new(source: impl Into<String>, key: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+            key: key.into(),
+        }
+    }
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.source, self.key)
+    }
+}
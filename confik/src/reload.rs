@@ -0,0 +1,76 @@
+//! Live-reloading configuration, for long-running services that want to pick up config
+//! changes without restarting.
+//!
+//! `confik` otherwise builds a [`Configuration`](crate::Configuration) once and is done;
+//! [`ReloadableConfig`] keeps a [`ConfigBuilder`](crate::ConfigBuilder) around, watches
+//! its file-backed sources, and re-runs the merge whenever one of them changes.
+//!
+//! Pulls in `notify` and `arc_swap`, so this module is gated behind the `watch` feature.
+
+use std::sync::Arc;
+
+use arc_swap::ArcSwap;
+use notify::{RecommendedWatcher, Watcher};
+
+use crate::{ConfigBuilder, Configuration};
+
+/// A config value that refreshes itself when a watched source file changes.
+///
+/// Built from a fully-specified [`ConfigBuilder<T>`] plus the paths of its watchable file
+/// sources. Holds the last-good `T` behind an [`arc_swap::ArcSwap`]; [`load`](Self::load)
+/// is a cheap, lock-free read of the current value.
+///
+/// A reload that fails to parse or validate keeps the previous value in place and is
+/// reported through the `on_error` callback rather than panicking — a malformed config
+/// write shouldn't take down a running service.
+pub struct ReloadableConfig<T> {
+    current: Arc<ArcSwap<T>>,
+    _watcher: RecommendedWatcher,
+}
+
+impl<T: Configuration + Send + Sync + 'static> ReloadableConfig<T> {
+    /// Performs an initial build of `builder`, then spawns a watcher on `watched_paths`
+    /// that re-runs the same builder's merge and publishes the result whenever one of
+    /// those paths changes.
+    ///
+    /// `on_error` is called with each reload's error instead of the failure propagating;
+    /// the previously published value is left in place.
+    pub fn watch(
+        builder: ConfigBuilder<T>,
+        watched_paths: impl IntoIterator<Item = std::path::PathBuf>,
+        on_error: impl Fn(Box<dyn std::error::Error + Send + Sync>) + Send + Sync + 'static,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let initial = builder.clone().try_build()?;
+        let current = Arc::new(ArcSwap::from_pointee(initial));
+
+        let rebuild_current = Arc::clone(&current);
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            if let Err(err) = event {
+                on_error(Box::new(err));
+                return;
+            }
+            match builder.clone().try_build() {
+                Ok(next) => rebuild_current.store(Arc::new(next)),
+                Err(err) => on_error(Box::new(err)),
+            }
+        })?;
+
+        for path in watched_paths {
+            watcher.watch(&path, notify::RecursiveMode::NonRecursive)?;
+        }
+
+        Ok(Self {
+            current,
+            _watcher: watcher,
+        })
+    }
+
+    /// Returns the most recently published value.
+    ///
+    /// This is a cheap, lock-free load; hold on to the returned `Arc` for the duration of
+    /// a single unit of work rather than calling `load` repeatedly, so that work sees a
+    /// consistent snapshot even if a reload happens concurrently.
+    pub fn load(&self) -> Arc<T> {
+        self.current.load_full()
+    }
+}
@@ -0,0 +1,70 @@
+//! A wrapper type that keeps secret values out of `Debug`/`Display` output.
+//!
+//! `confik` already distinguishes secret-carrying [`Source`](crate::Source)s via
+//! [`allows_secrets`](crate::Source::allows_secrets), but nothing stops the built
+//! [`Configuration`] from printing a secret field once it's deserialized. [`Secret<T>`]
+//! closes that gap for a single field: name it as the field's type and it stays
+//! redacted. A derive-level `#[confik(secret)]` attribute that wraps a field in
+//! `Secret<T>` automatically, without the caller spelling out the wrapper, would need
+//! changes to the `Configuration` derive macro, which isn't part of this crate.
+
+use std::{fmt, ops::Deref};
+
+use serde::Deserialize;
+
+use crate::Configuration;
+
+/// Wraps a field so its `Debug`/`Display` output is always redacted, while still
+/// deserializing and dereferencing to the inner value.
+///
+/// # Examples
+///
+/// ```
+/// use confik::Secret;
+///
+/// let token = Secret::new("hunter2".to_string());
+/// assert_eq!(format!("{token:?}"), "\"***\"");
+/// assert_eq!(&*token, "hunter2");
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(transparent)]
+pub struct Secret<T>(T);
+
+impl<T> Secret<T> {
+    /// Wraps `value` so it's redacted on `Debug`/`Display`.
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Unwraps the value, discarding redaction.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Secret<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> fmt::Debug for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\"***\"")
+    }
+}
+
+impl<T> fmt::Display for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "***")
+    }
+}
+
+impl<T> Configuration for Secret<T>
+where
+    Self: serde::de::DeserializeOwned,
+{
+    type Builder = Option<Self>;
+}
@@ -2,7 +2,7 @@ use std::error::Error;
 
 use envious::Config;
 
-use crate::{ConfigurationBuilder, Source};
+use crate::{provenance::Origin, ConfigurationBuilder, Source};
 
 /// A [`Source`] referring to environment variables.
 ///
@@ -35,6 +35,16 @@ use crate::{ConfigurationBuilder, Source};
 pub struct EnvSource<'a> {
     config: Config<'a>,
     allow_secrets: bool,
+    map_fields: Vec<MapFieldSpec>,
+    prefix: Option<&'a str>,
+    separator: &'a str,
+}
+
+/// A field registered via [`EnvSource::with_map_field`].
+#[derive(Debug, Clone)]
+struct MapFieldSpec {
+    field: String,
+    separator: String,
 }
 
 impl<'a> Default for EnvSource<'a> {
@@ -49,6 +59,9 @@ impl<'a> EnvSource<'a> {
         Self {
             config: Config::new(),
             allow_secrets: false,
+            map_fields: Vec::new(),
+            prefix: None,
+            separator: "_",
         }
     }
 
@@ -57,6 +70,7 @@ impl<'a> EnvSource<'a> {
     /// See [`Config::with_prefix`].
     pub fn with_prefix(mut self, prefix: &'a str) -> Self {
         self.config.with_prefix(prefix);
+        self.prefix = Some(prefix);
         self
     }
 
@@ -65,6 +79,7 @@ impl<'a> EnvSource<'a> {
     /// See [`Config::with_separator`].
     pub fn with_separator(mut self, separator: &'a str) -> Self {
         self.config.with_separator(separator);
+        self.separator = separator;
         self
     }
 
@@ -79,6 +94,152 @@ impl<'a> EnvSource<'a> {
         self.allow_secrets = true;
         self
     }
+
+    /// Converts a dotted [`Configuration`](crate::Configuration) key path (e.g.
+    /// `"port"`) into the env var name this source would read it from (e.g. `"PORT"`,
+    /// or `"APP_PORT"` with a prefix of `"APP"`).
+    fn env_key_for(&self, path: &str) -> String {
+        let key = path.to_uppercase().replace('.', self.separator);
+        match self.prefix {
+            Some(prefix) => format!("{prefix}{sep}{key}", sep = self.separator),
+            None => key,
+        }
+    }
+
+    /// Registers `field` as a dynamically-keyed map field (e.g. `HashMap<String, T>`)
+    /// whose keys aren't known ahead of time.
+    ///
+    /// envious' fixed prefix/separator scheme can only populate fields it already knows
+    /// the name of, so a map field has to be discovered instead: every environment
+    /// variable of the form `<prefix><separator><FIELD><separator><KEY>...` is collected,
+    /// `KEY` is normalized (uppercased, `-` replaced with `_`) into the map key, and the
+    /// remaining suffix is handed to envious as normal so nested values within an entry
+    /// still work.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use confik::{ConfigBuilder, Configuration, EnvSource};
+    /// use std::collections::HashMap;
+    ///
+    /// #[derive(Configuration)]
+    /// struct Config {
+    ///     routes: HashMap<String, String>,
+    /// }
+    ///
+    /// std::env::set_var("APP_ROUTES_HOME", "/");
+    /// std::env::set_var("APP_ROUTES_ABOUT-US", "/about");
+    ///
+    /// let config = ConfigBuilder::<Config>::default()
+    ///     .override_with(
+    ///         EnvSource::new()
+    ///             .with_prefix("APP")
+    ///             .with_map_field("ROUTES", "_"),
+    ///     )
+    ///     .try_build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.routes.get("HOME"), Some(&"/".to_string()));
+    /// assert_eq!(config.routes.get("ABOUT_US"), Some(&"/about".to_string()));
+    /// ```
+    pub fn with_map_field(mut self, field: impl Into<String>, separator: impl Into<String>) -> Self {
+        self.map_fields.push(MapFieldSpec {
+            field: field.into(),
+            separator: separator.into(),
+        });
+        self
+    }
+
+    /// Scans `std::env::vars()` for the registered [`map_fields`](Self::with_map_field),
+    /// normalizing each discovered key, and returns the env vars envious should be built
+    /// from: the untouched vars plus the normalized, re-keyed map entries.
+    ///
+    /// Respects [`with_prefix`](Self::with_prefix): the configured prefix is stripped
+    /// before a var is matched against a map field's name, and re-attached afterwards, so
+    /// a prefixed variable (e.g. `APP_ROUTES_HOME`) isn't checked against `spec.field`
+    /// with the app's own prefix still attached to its first segment.
+    ///
+    /// A map key is just the first remaining segment (e.g. `HOME` in `APP_ROUTES_HOME`);
+    /// anything past it is treated as a nested path within that entry (e.g. `PATH` in
+    /// `APP_ROUTES_HOME_PATH`, for a map of structs) and passed through unnormalized.
+    fn normalized_env_vars(&self) -> Vec<(String, String)> {
+        std::env::vars()
+            .map(|(key, value)| {
+                let Some(rest) = self.strip_configured_prefix(&key) else {
+                    return (key, value);
+                };
+
+                for spec in &self.map_fields {
+                    let Some((field_name, rest)) = rest.split_once(&spec.separator) else {
+                        continue;
+                    };
+                    if !field_name.eq_ignore_ascii_case(&spec.field) || rest.is_empty() {
+                        continue;
+                    }
+                    let (map_key, nested_rest) = match rest.split_once(&spec.separator) {
+                        Some((map_key, nested_rest)) => (map_key, Some(nested_rest)),
+                        None => (rest, None),
+                    };
+                    let normalized_key = map_key.to_uppercase().replace('-', "_");
+                    let rebuilt = match nested_rest {
+                        Some(nested_rest) => format!(
+                            "{field_name}{sep}{normalized_key}{sep}{nested_rest}",
+                            sep = spec.separator
+                        ),
+                        None => {
+                            format!("{field_name}{sep}{normalized_key}", sep = spec.separator)
+                        }
+                    };
+                    return (self.reattach_prefix(&rebuilt), value);
+                }
+                (key, value)
+            })
+            .collect()
+    }
+
+    /// Strips the configured prefix (and the separator that follows it) from `key`,
+    /// returning `None` if `key` doesn't start with it. With no prefix configured, `key`
+    /// is returned unchanged.
+    fn strip_configured_prefix<'k>(&self, key: &'k str) -> Option<&'k str> {
+        match self.prefix {
+            Some(prefix) => key.strip_prefix(prefix)?.strip_prefix(self.separator),
+            None => Some(key),
+        }
+    }
+
+    /// The inverse of [`strip_configured_prefix`](Self::strip_configured_prefix).
+    fn reattach_prefix(&self, rest: &str) -> String {
+        match self.prefix {
+            Some(prefix) => format!("{prefix}{sep}{rest}", sep = self.separator),
+            None => rest.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalized_env_vars_matches_single_segment_map_keys() {
+        std::env::set_var("APP_ROUTES_HOME", "/");
+        std::env::set_var("APP_ROUTES_ABOUT-US", "/about");
+
+        let source = EnvSource::new()
+            .with_prefix("APP")
+            .with_map_field("ROUTES", "_");
+        let normalized = source.normalized_env_vars();
+
+        std::env::remove_var("APP_ROUTES_HOME");
+        std::env::remove_var("APP_ROUTES_ABOUT-US");
+
+        assert!(normalized
+            .iter()
+            .any(|(key, value)| key == "APP_ROUTES_HOME" && value == "/"));
+        assert!(normalized
+            .iter()
+            .any(|(key, value)| key == "APP_ROUTES_ABOUT_US" && value == "/about"));
+    }
 }
 
 impl<'a> Source for EnvSource<'a> {
@@ -87,6 +248,15 @@ impl<'a> Source for EnvSource<'a> {
     }
 
     fn provide<T: ConfigurationBuilder>(&self) -> Result<T, Box<dyn Error + Sync + Send>> {
-        Ok(self.config.build_from_env()?)
+        if self.map_fields.is_empty() {
+            return Ok(self.config.build_from_env()?);
+        }
+
+        Ok(self.config.build_from_iter(self.normalized_env_vars())?)
+    }
+
+    fn origin_for(&self, path: &str) -> Option<Origin> {
+        let key = self.env_key_for(path);
+        std::env::var(&key).ok().map(|_| Origin::new("EnvSource", key))
     }
 }
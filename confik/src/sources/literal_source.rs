@@ -0,0 +1,62 @@
+use std::error::Error;
+
+use serde::Serialize;
+
+use crate::{ConfigurationBuilder, Source};
+
+/// A [`Source`] that contributes a single value at a dotted key path.
+///
+/// Lets a caller inject one computed value (a derived data dir, a CLI-parsed flag) into a
+/// [`ConfigBuilder`](crate::ConfigBuilder) without authoring a bespoke `Source`.
+/// [`ConfigBuilder::set_default`](crate::ConfigBuilder::set_default) and
+/// [`ConfigBuilder::set_override`](crate::ConfigBuilder::set_override) build one of these
+/// and slot it at the bottom and top of the merge precedence, respectively; there's
+/// normally no need to construct one directly.
+///
+/// # Examples
+///
+/// ```
+/// use confik::sources::LiteralSource;
+///
+/// let source = LiteralSource::new("database.port", 5432).unwrap();
+/// ```
+///
+/// # Secrets
+///
+/// Secrets are allowed, since the caller controls the value directly.
+#[derive(Debug, Clone)]
+pub struct LiteralSource {
+    path: String,
+    value: serde_json::Value,
+}
+
+impl LiteralSource {
+    /// Creates a source contributing `value` at `path`, a dot-separated key path (e.g.
+    /// `"database.port"`) matching the target field's location in the `Configuration`.
+    pub fn new(path: impl Into<String>, value: impl Serialize) -> Result<Self, serde_json::Error> {
+        Ok(Self {
+            path: path.into(),
+            value: serde_json::to_value(value)?,
+        })
+    }
+}
+
+impl Source for LiteralSource {
+    fn allows_secrets(&self) -> bool {
+        true
+    }
+
+    fn provide<T: ConfigurationBuilder>(&self) -> Result<T, Box<dyn Error + Sync + Send>> {
+        Ok(serde_json::from_value(nest(&self.path, self.value.clone()))?)
+    }
+}
+
+/// Wraps `value` in nested single-key objects, one per `.`-separated segment of `path`.
+fn nest(path: &str, value: serde_json::Value) -> serde_json::Value {
+    let mut map = serde_json::Map::new();
+    match path.split_once('.') {
+        Some((head, rest)) => map.insert(head.to_string(), nest(rest, value)),
+        None => map.insert(path.to_string(), value),
+    };
+    serde_json::Value::Object(map)
+}
@@ -0,0 +1,9 @@
+//! Built-in [`Source`](crate::Source) implementations.
+
+mod env_source;
+mod literal_source;
+mod profiled_source;
+
+pub use env_source::EnvSource;
+pub use literal_source::LiteralSource;
+pub use profiled_source::{ProfileAwareSource, ProfiledSource, CONFIK_PROFILE_ENV};
@@ -0,0 +1,125 @@
+use std::error::Error;
+
+use crate::{ConfigurationBuilder, Source};
+
+/// Env var consulted for the active profile when [`ConfigBuilder::with_profile`] isn't
+/// called explicitly.
+///
+/// [`ConfigBuilder::with_profile`]: crate::ConfigBuilder::with_profile
+pub const CONFIK_PROFILE_ENV: &str = "CONFIK_PROFILE";
+
+/// A [`Source`] that only contributes to a particular deployment profile.
+///
+/// Wraps an inner [`ProfileAwareSource`] that exposes a base section plus one or more
+/// named profile sections (e.g. a `[profile.prod]` table, or an env namespace split on
+/// profile). `ProfiledSource` provides the base section, then overlays the section for
+/// whichever profile it was constructed with, so the caller's merge order stays:
+/// defaults → base → active profile → explicit overrides.
+///
+/// # Examples
+///
+/// ```
+/// use confik::{sources::ProfileAwareSource, ConfigBuilder, Configuration, Source};
+///
+/// struct StaticSections;
+///
+/// impl Source for StaticSections {
+///     fn allows_secrets(&self) -> bool {
+///         false
+///     }
+///
+///     fn provide<T: confik::ConfigurationBuilder>(
+///         &self,
+///     ) -> Result<T, Box<dyn std::error::Error + Sync + Send>> {
+///         self.provide_base()
+///     }
+/// }
+///
+/// impl ProfileAwareSource for StaticSections {
+///     fn provide_base<T: confik::ConfigurationBuilder>(
+///         &self,
+///     ) -> Result<T, Box<dyn std::error::Error + Sync + Send>> {
+///         Ok(serde_json::from_value(serde_json::json!({ "port": 80 }))?)
+///     }
+///
+///     fn provide_profile<T: confik::ConfigurationBuilder>(
+///         &self,
+///         profile: &str,
+///     ) -> Result<T, Box<dyn std::error::Error + Sync + Send>> {
+///         match profile {
+///             "prod" => Ok(serde_json::from_value(serde_json::json!({ "port": 443 }))?),
+///             _ => Ok(T::default()),
+///         }
+///     }
+/// }
+///
+/// #[derive(Configuration)]
+/// struct Config {
+///     port: u16,
+/// }
+///
+/// let config = ConfigBuilder::<Config>::default()
+///     .with_profile("prod")
+///     .override_with_profiled(StaticSections)
+///     .try_build()
+///     .unwrap();
+///
+/// assert_eq!(config.port, 443);
+/// ```
+///
+/// # Secrets
+///
+/// Secrets are allowed if the wrapped source allows them.
+#[derive(Debug, Clone)]
+pub struct ProfiledSource<S> {
+    inner: S,
+    profile: String,
+}
+
+impl<S> ProfiledSource<S> {
+    /// Wraps `inner`, scoping it down to the base section plus `profile`'s section.
+    pub fn new(inner: S, profile: impl Into<String>) -> Self {
+        Self {
+            inner,
+            profile: profile.into(),
+        }
+    }
+
+    /// Wraps `inner`, using [`CONFIK_PROFILE_ENV`] to pick the active profile.
+    ///
+    /// Falls back to `default_profile` if the env var isn't set.
+    pub fn from_env_or(inner: S, default_profile: impl Into<String>) -> Self {
+        let profile = std::env::var(CONFIK_PROFILE_ENV).unwrap_or_else(|_| default_profile.into());
+        Self::new(inner, profile)
+    }
+}
+
+/// A [`Source`] whose underlying data is split into a shared base section and one or
+/// more profile-specific sections, such as a config file with a `[profile.$NAME]` table
+/// per deployment target.
+///
+/// Implemented by sources that [`ProfiledSource`] can wrap. Sources without profile
+/// sections (or that don't have a section for the requested profile) should return
+/// `T::default()` from [`provide_profile`](Self::provide_profile).
+pub trait ProfileAwareSource: Source {
+    /// Provides the base section, shared by every profile.
+    fn provide_base<T: ConfigurationBuilder>(&self) -> Result<T, Box<dyn Error + Sync + Send>>;
+
+    /// Provides the section scoped to `profile`, or `T::default()` if there is none.
+    fn provide_profile<T: ConfigurationBuilder>(
+        &self,
+        profile: &str,
+    ) -> Result<T, Box<dyn Error + Sync + Send>>;
+}
+
+impl<S: ProfileAwareSource> Source for ProfiledSource<S> {
+    fn allows_secrets(&self) -> bool {
+        self.inner.allows_secrets()
+    }
+
+    fn provide<T: ConfigurationBuilder>(&self) -> Result<T, Box<dyn Error + Sync + Send>> {
+        let base = self.inner.provide_base::<T>()?;
+        let profile = self.inner.provide_profile::<T>(&self.profile)?;
+        Ok(base.merge(profile))
+    }
+}